@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::Value;
+
+/// Why an expression failed to evaluate
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The source text isn't a valid expression
+    ParseError,
+    /// A name doesn't resolve where a value is required: a call or method
+    /// that is neither builtin nor registered, a multi-segment path, or an
+    /// unresolved identifier inside a collection literal (a bare unresolved
+    /// identifier evaluates to [`Value::None`] instead). Also carries the
+    /// source text of an expression kind that isn't supported at all.
+    UnknownIdent(String),
+    /// `op` isn't defined between `lhs` and `rhs`
+    TypeMismatch {
+        op: &'static str,
+        lhs: Value,
+        rhs: Value,
+    },
+    /// A method/function call had the wrong number or type of arguments, or
+    /// produced no value (e.g. `None.unwrap()`, or a host function returning
+    /// `None`)
+    Arity,
+    /// An index or slice range fell outside the collection's bounds
+    IndexOutOfBounds,
+    /// Both sides of `/` or `%` were integers and the right side was `0`
+    DivisionByZero,
+    /// An array/struct literal contained an element that evaluated to
+    /// `Value::None` - a concrete collection can't structurally hold "no
+    /// value", even when that `None` was explicit rather than an unresolved
+    /// identifier (which gets [`EvalError::UnknownIdent`] instead)
+    NullElement,
+    /// A JSON value passed to [`crate::Eval::from_json`] can't be parsed
+    /// back as an expression - that's a (nested) object whose key isn't a
+    /// valid Rust identifier, since objects are represented internally as
+    /// struct literals. Carries the top-level key whose value couldn't be
+    /// converted.
+    #[cfg(feature = "serde")]
+    InvalidJson(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::ParseError => write!(f, "invalid expression"),
+            EvalError::UnknownIdent(ident) => write!(f, "`{}` doesn't name a value", ident),
+            EvalError::TypeMismatch { op, lhs, rhs } => {
+                write!(f, "`{}` isn't defined between {} and {}", op, lhs, rhs)
+            }
+            EvalError::Arity => write!(f, "wrong number or type of arguments, or no value to return"),
+            EvalError::IndexOutOfBounds => write!(f, "index out of bounds"),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::NullElement => write!(f, "a collection can't contain a null value"),
+            #[cfg(feature = "serde")]
+            EvalError::InvalidJson(key) => {
+                write!(f, "the JSON value for `{}` can't be represented as an expression", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
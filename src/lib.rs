@@ -4,9 +4,9 @@
 //! All are option by default
 //!
 //! ```rust
-//! use v_eval::{Value, Eval};
+//! use v_eval::{Value, Eval, EvalError};
 //!
-//!# fn main() -> Result<(), ()> {
+//!# fn main() -> Result<(), EvalError> {
 //! let e = Eval::default()
 //!     .insert("foo", "true")?
 //!     .insert("string", "\"foo\"")?
@@ -30,8 +30,8 @@
 //! #### Option
 //! - `and`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -46,8 +46,8 @@
 //! ```
 //! - `is_none`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -61,8 +61,8 @@
 //! ```
 //! - `is_some`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -76,8 +76,8 @@
 //! ```
 //! - `or`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -92,8 +92,8 @@
 //! ```
 //! - `unwrap_or`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -108,8 +108,8 @@
 //! ```
 //! - `unwrap`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -124,8 +124,8 @@
 //! ```
 //! - `xor`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -143,8 +143,8 @@
 //! #### Dynamic type
 //! - `is_bool`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -158,8 +158,8 @@
 //! ```
 //! - `is_float`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -173,8 +173,8 @@
 //! ```
 //! - `is_int`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -188,8 +188,8 @@
 //! ```
 //! - `is_option`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -205,8 +205,8 @@
 //! ```
 //! - `is_range`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -220,8 +220,8 @@
 //! ```
 //! - `is_str`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -235,8 +235,8 @@
 //! ```
 //! - `is_vec`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -248,10 +248,23 @@
 //!# Ok(())
 //!# }
 //! ```
+//! - `is_map`
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//!# let e = Eval::default()
+//!#     .insert("foo", "true")?
+//!#     .insert("user", "User { age: 20 }")?;
+//!#
+//! assert_eq!(e.eval("user.is_map()").unwrap(), Value::Bool(true));
+//! assert_eq!(e.eval("foo.is_map()").unwrap(), Value::Bool(false));
+//!# Ok(())
+//!# }
+//! ```
 //! - `is_same`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -306,8 +319,8 @@
 //! - `to_radians`
 //! - `trunc`
 //! ```rust
-//!# use v_eval::{Value, Eval};
-//!# fn main() -> Result<(), ()> {
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
 //!# let e = Eval::default()
 //!#     .insert("foo", "true")?
 //!#     .insert("string", "\"foo\"")?
@@ -319,6 +332,108 @@
 //!# }
 //! ```
 //!
+//! `Int` and `Float` freely mix in arithmetic and comparisons, widening to `Float`
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//!# let e = Eval::default();
+//! assert_eq!(e.eval("1 == 1.0").unwrap(), Value::Bool(true));
+//! assert_eq!(e.eval("2 + 0.5").unwrap(), Value::Float(2.5));
+//! assert_eq!(e.eval("3 < 2.0").unwrap(), Value::Bool(false));
+//!# Ok(())
+//!# }
+//! ```
+//!
+//! ## Null-coalescing `??`
+//! Loosest-binding of all operators, `??` returns the first side that isn't
+//! `None`, without requiring both sides to share a type, and never
+//! evaluates its right side unless the left side is `None`
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//!# let e = Eval::default().insert("bar", "false")?.insert("foo", "true")?;
+//! assert_eq!(e.eval("not_exist ?? bar ?? 0").unwrap(), Value::Bool(false));
+//! assert_eq!(e.eval("not_exist ?? 0").unwrap(), Value::Int(0));
+//! assert_eq!(e.eval("bar.is_null()").unwrap(), Value::Bool(false));
+//! assert_eq!(e.eval("not_exist.is_null()").unwrap(), Value::Bool(true));
+//!
+//! // binds looser than `&&`: this is `foo ?? (false && false)`, not
+//! // `(foo ?? false) && false`, so the defined `foo` wins outright
+//! assert_eq!(e.eval("foo ?? false && false").unwrap(), Value::Bool(true));
+//!
+//! // short-circuits: `foo` is defined, so `1 + true` is never evaluated
+//! assert_eq!(e.eval("foo ?? 1 + true").unwrap(), Value::Bool(true));
+//!# Ok(())
+//!# }
+//! ```
+//!
+//! ## Object/attribute access
+//! Keyed data is built with Rust's own struct-literal syntax (the struct
+//! name is never looked up, so any name will do) and read back with `.`,
+//! the same way askama resolves `Expr::Attr` over template data
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//!# let e = Eval::default().insert("user", "User { age: 20, name: \"foo\" }")?;
+//! assert_eq!(e.eval("user.age > 18 && user.name == \"foo\"").unwrap(), Value::Bool(true));
+//! assert_eq!(e.eval("user.missing"), None);
+//!# Ok(())
+//!# }
+//! ```
+//!
+//! ## Functions
+//! Host functions can be registered under a name and called from expressions,
+//! the same way builtin methods are
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//! let e = Eval::default().function("clamp", |args: &[Value]| match args {
+//!     [Value::Int(v), Value::Int(min), Value::Int(max)] => Some(Value::Int((*v).clamp(*min, *max))),
+//!     _ => None,
+//! });
+//!
+//! assert_eq!(e.eval("clamp(5, 0, 3)").unwrap(), Value::Int(3));
+//!# Ok(())
+//!# }
+//! ```
+//!
+//! ## Sequenced bindings
+//! A small block of `let` bindings can be evaluated in one call, each one
+//! seeing the names bound before it, so a shared subexpression is only
+//! computed once
+//! ```rust
+//!# use v_eval::{Value, Eval, EvalError};
+//!# fn main() -> Result<(), EvalError> {
+//!# let e = Eval::default().insert("fon", "1")?;
+//! assert_eq!(
+//!     e.eval_block("let x = 2 * fon; let y = x + 1; y")?,
+//!     Value::Int(3)
+//! );
+//!# Ok(())
+//!# }
+//! ```
+//!
+//! ## JSON interop
+//! With the `serde` feature enabled, a whole JSON object can seed the context
+//! in one call, and results can be turned back into `serde_json::Value`
+//! ```rust
+//!# #[cfg(feature = "serde")]
+//!# fn main() {
+//! use v_eval::{Eval, Value};
+//!
+//! let obj = serde_json::json!({ "user": { "age": 20, "name": "foo" } });
+//! let e = Eval::from_json(obj.as_object().unwrap().clone()).unwrap();
+//!
+//! assert_eq!(e.eval("user.age > 18").unwrap(), Value::Bool(true));
+//! assert_eq!(
+//!     serde_json::Value::from(e.eval("user.name").unwrap()),
+//!     serde_json::json!("foo")
+//! );
+//!# }
+//!# #[cfg(not(feature = "serde"))]
+//!# fn main() {}
+//! ```
+//!
 //!
 
 extern crate quote_impersonated as quote;
@@ -326,32 +441,100 @@ extern crate syn_impersonated as syn;
 
 use std::collections::BTreeMap;
 
-use syn::parse_str;
+use syn::{parse_str, Block, Pat, Stmt};
 
+mod error;
+#[cfg(feature = "serde")]
+mod json;
 mod method;
 mod operator;
 mod reflect;
 mod value;
 
-pub use self::{reflect::eval, value::Value};
+pub use self::{error::EvalError, reflect::eval, value::Value};
+
+/// Rewrite the non-Rust `??` coalescing operator to `=` (otherwise unused by
+/// this crate) so `syn` can parse it as an ordinary expression
+///
+/// `=` is the loosest-binding, right-associative construct in Rust's
+/// grammar - looser than `||`/`&&`/comparisons, which a real Rust *operator*
+/// like `|` isn't - so this is what actually delivers `??`'s documented
+/// "loosest of all operators" precedence. `reflect::eval_with` reads the
+/// resulting `Expr::Assign` as a coalesce, evaluating the right side (and
+/// surfacing its errors) only when the left side is `Value::None`.
+///
+/// Skips over string-literal spans so a `??` that's part of the *text* of a
+/// string (`"a??b"`) isn't rewritten along with it - only `??` appearing as
+/// actual source syntax is a coalesce.
+fn desugar(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut chars = src.chars().peekable();
+    let mut in_str = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_str = !in_str;
+                out.push(c);
+            }
+            '\\' if in_str => {
+                out.push(c);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '?' if !in_str && chars.peek() == Some(&'?') => {
+                chars.next();
+                out.push('=');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
 
 /// Evaluator with context
-pub struct Eval(BTreeMap<String, syn::Expr>);
+pub struct Eval(BTreeMap<String, syn::Expr>, reflect::Functions);
 
 impl Default for Eval {
     fn default() -> Self {
-        Self(BTreeMap::new())
+        Self(BTreeMap::new(), BTreeMap::new())
     }
 }
 
 impl Eval {
     pub fn new(c: BTreeMap<String, syn::Expr>) -> Self {
-        Self(c)
+        Self(c, BTreeMap::new())
+    }
+
+    /// Build a context from a JSON object, mapping each key to its value
+    ///
+    /// JSON scalars, arrays and (nested) objects all become the matching
+    /// [`Value`] variant (`null` becomes [`Value::None`], an object becomes
+    /// [`Value::Map`]), so its fields can be read with `.` access. A `null`
+    /// nested inside an array or object is accepted here, but the entry
+    /// holding it then fails to evaluate with [`EvalError::NullElement`],
+    /// since a collection literal can't hold `None`.
+    ///
+    /// Fails with [`EvalError::InvalidJson`] (naming the offending top-level
+    /// key) if a (nested) object has a key that isn't a valid Rust
+    /// identifier, since objects are represented internally as struct
+    /// literals and can't be re-parsed back from one otherwise.
+    #[cfg(feature = "serde")]
+    pub fn from_json(obj: serde_json::Map<String, serde_json::Value>) -> Result<Self, EvalError> {
+        let mut ctx = BTreeMap::new();
+        for (k, v) in obj {
+            let value = Value::from(v);
+            let expr = parse_str::<syn::Expr>(&value.literal())
+                .map_err(|_| EvalError::InvalidJson(k.clone()))?;
+            ctx.insert(k, expr);
+        }
+
+        Ok(Self::new(ctx))
     }
 
     /// Parse and insert in context name - syn::Expr
-    pub fn insert(mut self, k: &str, v: &str) -> Result<Self, ()> {
-        let e = parse_str::<syn::Expr>(v).map_err(|_| ())?;
+    pub fn insert(mut self, k: &str, v: &str) -> Result<Self, EvalError> {
+        let e = parse_str::<syn::Expr>(&desugar(v)).map_err(|_| EvalError::ParseError)?;
         self.0.insert(k.to_owned(), e);
 
         Ok(self)
@@ -364,11 +547,72 @@ impl Eval {
         self
     }
 
+    /// Register a host function, callable from expressions by name
+    ///
+    /// Builtin methods always take priority: in method position a registered
+    /// function only runs when no builtin method has its name (a builtin
+    /// whose arguments don't fit is an error, not a fall-back to the
+    /// registered function). A call expression (`f(a, b)`) invokes
+    /// it directly; a method expression (`a.f(b)`) passes the receiver as the
+    /// first argument. The closure receives already-evaluated arguments and
+    /// should return `None` on arity or type mismatch.
+    pub fn function<F>(mut self, name: &str, f: F) -> Self
+    where
+        F: Fn(&[Value]) -> Option<Value> + 'static,
+    {
+        self.1.insert(name.to_owned(), Box::new(f));
+
+        self
+    }
+
+    /// Evaluate expression with current context, reporting why it failed
+    pub fn try_eval(&self, src: &str) -> Result<Value, EvalError> {
+        let src = parse_str::<syn::Expr>(&desugar(src)).map_err(|_| EvalError::ParseError)?;
+
+        reflect::eval_with(&self.0, &self.1, &src)
+    }
+
     /// Evaluate expression with current context
+    ///
+    /// A convenience wrapper over [`Eval::try_eval`] for callers who only
+    /// care whether a value came back, collapsing errors and a bare
+    /// [`Value::None`] result alike to `None`. Use `try_eval` to see why an
+    /// expression failed.
     pub fn eval(&self, src: &str) -> Option<Value> {
-        parse_str::<syn::Expr>(src)
-            .ok()
-            .and_then(|src| eval(&self.0, &src))
+        self.try_eval(src).ok().filter(|v| *v != Value::None)
+    }
+
+    /// Evaluate a sequence of `let` bindings followed by a trailing
+    /// expression, e.g. `"let x = 2 * fon; let y = x + 1; y"`
+    ///
+    /// Each binding's right-hand side is evaluated once, against a scratch
+    /// context that starts as a copy of `self`'s and accumulates every
+    /// binding made so far, without mutating `self`.
+    pub fn eval_block(&self, src: &str) -> Result<Value, EvalError> {
+        let block = parse_str::<Block>(&format!("{{ {} }}", desugar(src))).map_err(|_| EvalError::ParseError)?;
+
+        let mut scratch = self.0.clone();
+        let mut tail = None;
+
+        for stmt in block.stmts {
+            match stmt {
+                Stmt::Local(local) => {
+                    let name = match local.pat {
+                        Pat::Ident(p) => p.ident.to_string(),
+                        _ => return Err(EvalError::ParseError),
+                    };
+                    let (_, init) = local.init.ok_or(EvalError::ParseError)?;
+                    let value = reflect::eval_with(&scratch, &self.1, &init)?;
+                    let expr = parse_str::<syn::Expr>(&value.literal()).map_err(|_| EvalError::ParseError)?;
+
+                    scratch.insert(name, expr);
+                }
+                Stmt::Expr(expr) | Stmt::Semi(expr, _) => tail = Some(expr),
+                Stmt::Item(_) => return Err(EvalError::ParseError),
+            }
+        }
+
+        reflect::eval_with(&scratch, &self.1, &tail.ok_or(EvalError::ParseError)?)
     }
 }
 
@@ -378,13 +622,14 @@ mod test {
 
     #[allow(clippy::cognitive_complexity)]
     #[test]
-    fn test() -> Result<(), ()> {
+    fn test() -> Result<(), EvalError> {
         let e = Eval::default()
             .insert("foo", "true")?
             .insert("fon", "1")?
             .insert("s", r#""foo""#)?
             .insert("arr", "[1, 2]")?
-            .insert("bar", "false")?;
+            .insert("bar", "false")?
+            .insert("user", "User { age: 20, name: \"foo\" }")?;
 
         assert_eq!(e.eval("foo != bar").unwrap(), Value::Bool(true));
         assert_eq!(
@@ -479,6 +724,99 @@ mod test {
         assert_eq!(e.eval("not_exist"), None);
         assert_eq!(e.eval(r#"&[ "foo", self.s]"#), None);
 
+        // an unresolved identifier in a collection literal is reported as
+        // such, distinct from a collection literally holding `None`
+        assert_eq!(
+            e.try_eval("&[true, not_exist]"),
+            Err(EvalError::UnknownIdent("not_exist".into()))
+        );
+        assert_eq!(e.try_eval("&[true, None]"), Err(EvalError::NullElement));
+
+        // integer division/remainder by zero is an error, not a panic
+        assert_eq!(e.try_eval("1 / 0"), Err(EvalError::DivisionByZero));
+        assert_eq!(e.try_eval("5 % 0"), Err(EvalError::DivisionByZero));
+        // a float divisor of zero is still well-defined, so it's unaffected
+        assert_eq!(e.eval("1 / 0.0").unwrap(), Value::Float(f64::INFINITY));
+
+        assert_eq!(
+            e.eval_block("let x = 2 * fon; let y = x + 1; y").unwrap(),
+            Value::Int(3)
+        );
+        assert_eq!(
+            e.eval_block("let x = 1.5; x + 1.0").unwrap(),
+            Value::Float(2.5)
+        );
+        assert!(e.eval_block("let x = 1 + true; x").is_err());
+
+        assert_eq!(e.eval("1 == 1.0").unwrap(), Value::Bool(true));
+        assert_eq!(e.eval("2 + 0.5").unwrap(), Value::Float(2.5));
+        assert_eq!(e.eval("3 < 2.0").unwrap(), Value::Bool(false));
+        assert_eq!(e.eval("2.0 * 3").unwrap(), Value::Float(6.0));
+
+        assert_eq!(e.eval("not_exist ?? bar ?? 0").unwrap(), Value::Bool(false));
+        assert_eq!(e.eval("not_exist ?? 0").unwrap(), Value::Int(0));
+        assert_eq!(e.eval("foo ?? not_exist").unwrap(), Value::Bool(true));
+        assert_eq!(e.eval("foo.is_null()").unwrap(), Value::Bool(false));
+        assert_eq!(e.eval("not_exist.is_null()").unwrap(), Value::Bool(true));
+
+        // `??` binds looser than `&&`: `foo ?? (false && false)`, not
+        // `(foo ?? false) && false` - the two would disagree here
+        assert_eq!(e.eval("foo ?? false && false").unwrap(), Value::Bool(true));
+        assert_eq!(e.eval("not_exist ?? true && false").unwrap(), Value::Bool(false));
+        // short-circuits: `foo` is defined, so the invalid right side is
+        // never evaluated and never errors
+        assert_eq!(e.eval("foo ?? 1 + true").unwrap(), Value::Bool(true));
+        assert!(e.try_eval("not_exist ?? 1 + true").is_err());
+        // a `??` that's part of a string literal's text is left alone, not
+        // rewritten as if it were the coalesce operator
+        assert_eq!(
+            e.eval(r#""a??b""#).unwrap(),
+            Value::Str("a??b".to_owned())
+        );
+        assert!(e.eval_block("x").is_ok());
+
+        assert_eq!(
+            e.eval("user.age > 18 && user.name == \"foo\"").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(e.eval("user.missing"), None);
+        assert_eq!(e.eval("not_exist.missing"), None);
+        assert_eq!(e.eval("user.is_map()").unwrap(), Value::Bool(true));
+        assert_eq!(e.eval("foo.is_map()").unwrap(), Value::Bool(false));
+        assert_eq!(
+            e.eval("User { age: 20, name: \"foo\" } == user").unwrap(),
+            Value::Bool(true)
+        );
+        assert!(e.eval("foo.age").is_none());
+
+        // struct-update syntax (`..base`) merges `base`'s fields, with
+        // explicit fields taking priority
+        assert_eq!(
+            e.eval("User { age: 21, ..user }").unwrap(),
+            Value::Map(
+                vec![
+                    ("age".to_owned(), Value::Int(21)),
+                    ("name".to_owned(), Value::Str("foo".into())),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        assert!(e.try_eval("User { ..foo }").is_err());
+
+        let funcs = Eval::default()
+            .function("double", |args: &[Value]| match args {
+                [Value::Int(v)] => Some(Value::Int(v * 2)),
+                _ => None,
+            })
+            .function("abs", |_: &[Value]| Some(Value::Int(-1)));
+
+        // method-call position dispatches to a registered function, with the
+        // receiver passed as its first argument
+        assert_eq!(funcs.eval("3.double()").unwrap(), Value::Int(6));
+        // builtins always take priority over a same-named registered function
+        assert_eq!(funcs.eval("(-3).abs()").unwrap(), Value::Int(3));
+
         Ok(())
     }
 
@@ -494,4 +832,21 @@ mod test {
             Some(Value::Float(1.0f64.log10()))
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_json() {
+        let obj = serde_json::json!({ "user": { "age": 20 } });
+        let e = Eval::from_json(obj.as_object().unwrap().clone()).unwrap();
+        assert_eq!(e.eval("user.age").unwrap(), Value::Int(20));
+
+        // a nested object key that isn't a valid Rust identifier can't be
+        // represented as a struct literal - this must fail loudly, naming
+        // the rejected top-level key, rather than silently dropping it
+        let obj = serde_json::json!({ "user": { "first name": "foo" } });
+        match Eval::from_json(obj.as_object().unwrap().clone()) {
+            Err(EvalError::InvalidJson(key)) => assert_eq!(key, "user"),
+            other => panic!("expected EvalError::InvalidJson, got {:?}", other.map(|_| ())),
+        }
+    }
 }
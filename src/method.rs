@@ -0,0 +1,178 @@
+use crate::{EvalError, Value};
+
+/// Methods whose numeric result is always rounded back down to a [`Value::Int`]
+const TO_INT: &[&str] = &["trunc", "ceil", "floor", "round", "signum"];
+
+/// Evaluate a method call already split into receiver/name/evaluated args
+///
+/// Returns `None` when `method` isn't a recognized builtin at all, so the
+/// caller can fall back to a registered host function; `Some(Err(_))` when
+/// it is recognized but the arguments don't match or it yields no value
+/// (e.g. `None.unwrap()`).
+pub(crate) fn eval(receiver: Value, method: &str, args: &[Value]) -> Option<Result<Value, EvalError>> {
+    if !is_builtin(method) {
+        return None;
+    }
+
+    let result = option_method(&receiver, method, args)
+        .or_else(|| dynamic_type_method(&receiver, method, args))
+        .or_else(|| number_method(&receiver, method, args));
+
+    Some(result.ok_or(EvalError::Arity))
+}
+
+fn is_builtin(method: &str) -> bool {
+    matches!(
+        method,
+        "and" | "or"
+            | "xor"
+            | "unwrap"
+            | "unwrap_or"
+            | "is_none"
+            | "is_null"
+            | "is_some"
+            | "is_bool"
+            | "is_int"
+            | "is_float"
+            | "is_str"
+            | "is_vec"
+            | "is_range"
+            | "is_map"
+            | "is_option"
+            | "is_same"
+            | "abs"
+            | "acos"
+            | "acosh"
+            | "asin"
+            | "asinh"
+            | "atan"
+            | "atanh"
+            | "atan2"
+            | "cbrt"
+            | "ceil"
+            | "cos"
+            | "cosh"
+            | "exp"
+            | "exp2"
+            | "exp_m1"
+            | "floor"
+            | "fract"
+            | "hypot"
+            | "ln"
+            | "ln_1p"
+            | "log"
+            | "log10"
+            | "log2"
+            | "max"
+            | "min"
+            | "powf"
+            | "powi"
+            | "recip"
+            | "round"
+            | "signum"
+            | "sin"
+            | "sinh"
+            | "sqrt"
+            | "tan"
+            | "tanh"
+            | "to_degrees"
+            | "to_radians"
+            | "trunc"
+    )
+}
+
+fn option_method(receiver: &Value, method: &str, args: &[Value]) -> Option<Value> {
+    match (method, args) {
+        ("and", [other]) => receiver.opt_and(other),
+        ("or", [other]) => receiver.opt_or(other),
+        ("xor", [other]) => receiver.xor(other),
+        ("unwrap", []) => receiver.clone().unwrap(),
+        ("unwrap_or", [other]) => Some(receiver.clone().unwrap_or(other)),
+        ("is_none", []) => Some(Value::Bool(receiver.is_none())),
+        ("is_null", []) => Some(Value::Bool(receiver.is_null())),
+        ("is_some", []) => Some(Value::Bool(receiver.is_some())),
+        _ => None,
+    }
+}
+
+fn dynamic_type_method(receiver: &Value, method: &str, args: &[Value]) -> Option<Value> {
+    match (method, args) {
+        ("is_bool", []) => Some(Value::Bool(receiver.is_bool())),
+        ("is_int", []) => Some(Value::Bool(receiver.is_int())),
+        ("is_float", []) => Some(Value::Bool(receiver.is_float())),
+        ("is_str", []) => Some(Value::Bool(receiver.is_str())),
+        ("is_vec", []) => Some(Value::Bool(receiver.is_vec())),
+        ("is_range", []) => Some(Value::Bool(receiver.is_range())),
+        ("is_map", []) => Some(Value::Bool(receiver.is_map())),
+        ("is_option", []) => Some(Value::Bool(receiver.is_option())),
+        ("is_same", [other]) => Some(Value::Bool(receiver.is_same(other))),
+        _ => None,
+    }
+}
+
+fn as_f64(v: &Value) -> Option<f64> {
+    match v {
+        Value::Int(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+fn number_method(receiver: &Value, method: &str, args: &[Value]) -> Option<Value> {
+    if method == "abs" && args.is_empty() {
+        return match receiver {
+            Value::Int(i) => Some(Value::Int(i.abs())),
+            Value::Float(f) => Some(Value::Float(f.abs())),
+            _ => None,
+        };
+    }
+
+    let n = as_f64(receiver)?;
+
+    let result = match (method, args) {
+        ("acos", []) => n.acos(),
+        ("acosh", []) => n.acosh(),
+        ("asin", []) => n.asin(),
+        ("asinh", []) => n.asinh(),
+        ("atan", []) => n.atan(),
+        ("atanh", []) => n.atanh(),
+        ("cbrt", []) => n.cbrt(),
+        ("ceil", []) => n.ceil(),
+        ("cos", []) => n.cos(),
+        ("cosh", []) => n.cosh(),
+        ("exp", []) => n.exp(),
+        ("exp2", []) => n.exp2(),
+        ("exp_m1", []) => n.exp_m1(),
+        ("floor", []) => n.floor(),
+        ("fract", []) => n.fract(),
+        ("ln", []) => n.ln(),
+        ("ln_1p", []) => n.ln_1p(),
+        ("log10", []) => n.log10(),
+        ("log2", []) => n.log2(),
+        ("recip", []) => n.recip(),
+        ("round", []) => n.round(),
+        ("signum", []) => n.signum(),
+        ("sin", []) => n.sin(),
+        ("sinh", []) => n.sinh(),
+        ("sqrt", []) => n.sqrt(),
+        ("tan", []) => n.tan(),
+        ("tanh", []) => n.tanh(),
+        ("to_degrees", []) => n.to_degrees(),
+        ("to_radians", []) => n.to_radians(),
+        ("trunc", []) => n.trunc(),
+        ("atan2", [other]) => n.atan2(as_f64(other)?),
+        ("hypot", [other]) => n.hypot(as_f64(other)?),
+        ("log", [base]) => n.log(as_f64(base)?),
+        ("powf", [exp]) => n.powf(as_f64(exp)?),
+        ("powi", [Value::Int(exp)]) => n.powi(*exp as i32),
+        ("max", [other]) => n.max(as_f64(other)?),
+        ("min", [other]) => n.min(as_f64(other)?),
+        _ => return None,
+    };
+
+    if TO_INT.contains(&method) {
+        Some(Value::Int(result as i64))
+    } else {
+        Some(Value::Float(result))
+    }
+}
@@ -2,7 +2,7 @@ use std::{cmp::Ordering, convert::TryFrom};
 
 use syn::BinOp;
 
-use crate::{reflect::Eval, Value};
+use crate::{reflect::Eval, EvalError, Value};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
@@ -52,6 +52,29 @@ impl Operator {
             _ => false,
         }
     }
+
+    /// The source-level symbol for this operator, for [`EvalError::TypeMismatch`]
+    fn symbol(self) -> &'static str {
+        match self {
+            ParenLeft => "(",
+            ParenRight => ")",
+            Not => "!",
+            Neg => "-",
+            Mul => "*",
+            Div => "/",
+            Rem => "%",
+            Add => "+",
+            Sub => "-",
+            Eq => "==",
+            Ne => "!=",
+            Gt => ">",
+            Lt => "<",
+            Ge => ">=",
+            Le => "<=",
+            And => "&&",
+            Or => "||",
+        }
+    }
 }
 
 impl TryFrom<syn::BinOp> for Operator {
@@ -78,9 +101,9 @@ impl TryFrom<syn::BinOp> for Operator {
 }
 
 impl Eval for Operator {
-    fn eval(self, stack: &mut Vec<Value>) -> Result<(), ()> {
-        let op2 = stack.pop().ok_or(())?;
-        let op1 = stack.pop().ok_or(())?;
+    fn eval(self, stack: &mut Vec<Value>) -> Result<(), EvalError> {
+        let op2 = stack.pop().ok_or(EvalError::Arity)?;
+        let op1 = stack.pop().ok_or(EvalError::Arity)?;
 
         macro_rules! _i {
             ($a:ident for $e:path) => {
@@ -93,14 +116,17 @@ impl Eval for Operator {
 
         macro_rules! order {
             ($($t:tt)+) => {
-                if let Some(a) = op1.partial_cmp(&op2) {
-                    _i!(a for $($t)+).into()
-                } else {
-                    return Err(());
+                match op1.partial_cmp(&op2) {
+                    Some(a) => _i!(a for $($t)+).into(),
+                    None => return Err(mismatch(self, op1, op2)),
                 }
             };
         }
 
+        if matches!(self, Div | Rem) && matches!((&op1, &op2), (Value::Int(_), Value::Int(0))) {
+            return Err(EvalError::DivisionByZero);
+        }
+
         if check_op(self, &op1, &op2) {
             stack.push(match self {
                 Add => op1 + op2,
@@ -122,25 +148,39 @@ impl Eval for Operator {
             });
             Ok(())
         } else {
-            Err(())
+            Err(mismatch(self, op1, op2))
         }
     }
 }
 
+fn mismatch(op: Operator, lhs: Value, rhs: Value) -> EvalError {
+    EvalError::TypeMismatch {
+        op: op.symbol(),
+        lhs,
+        rhs,
+    }
+}
+
+/// Are both operands `Int`/`Float`, in either combination?
+#[inline]
+fn numeric_pair(op1: &Value, op2: &Value) -> bool {
+    op1.is_numeric() && op2.is_numeric()
+}
+
 #[inline]
 fn check_op(op: Operator, op1: &Value, op2: &Value) -> bool {
     match op1 {
         Value::Int(_) => match op {
             Mul => match op2 {
                 Value::Str(_) => true,
-                _ => op1.is_same(op2),
+                _ => numeric_pair(op1, op2),
             },
-            Add | Sub | Div | Rem | Eq | Ne | Gt | Ge | Lt | Le => op1.is_same(op2),
+            Add | Sub | Div | Rem | Eq | Ne | Gt | Ge | Lt | Le => numeric_pair(op1, op2),
             Neg => *op2 == Value::Int(0),
             _ => false,
         },
         Value::Float(_) => match op {
-            Add | Mul | Sub | Div | Rem | Eq | Ne | Gt | Ge | Lt | Le => op1.is_same(op2),
+            Add | Mul | Sub | Div | Rem | Eq | Ne | Gt | Ge | Lt | Le => numeric_pair(op1, op2),
             Neg => *op2 == Value::Int(0),
             _ => false,
         },
@@ -152,7 +192,7 @@ fn check_op(op: Operator, op1: &Value, op2: &Value) -> bool {
             Add | Eq | Ne => op1.is_same(op2),
             _ => false,
         },
-        Value::Range(_) | Value::Vec(_) => match op {
+        Value::Range(_) | Value::Vec(_) | Value::Map(_) => match op {
             Eq | Ne => op1.is_same(op2),
             _ => false,
         },
@@ -161,7 +201,7 @@ fn check_op(op: Operator, op1: &Value, op2: &Value) -> bool {
             Not => *op2 == Value::Bool(false),
             _ => false,
         },
-        Value::None => false,
+        Value::None | Value::Option(_) => false,
     }
 }
 
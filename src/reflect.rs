@@ -0,0 +1,267 @@
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+use syn::{Expr, Lit, UnOp};
+
+use crate::{method, EvalError, Value};
+
+/// A host-defined function registered through [`crate::Eval::function`]
+pub(crate) type Function = Box<dyn Fn(&[Value]) -> Option<Value>>;
+
+pub(crate) type Functions = BTreeMap<String, Function>;
+
+/// Consume a 2-value stack and push the result, the way [`Operator`](crate::operator::Operator)
+/// does for every binary/unary operator
+pub(crate) trait Eval {
+    fn eval(self, stack: &mut Vec<Value>) -> Result<(), EvalError>;
+}
+
+/// Evaluate `expr` against `ctx`, without any host functions registered
+pub fn eval(ctx: &BTreeMap<String, Expr>, expr: &Expr) -> Option<Value> {
+    eval_with(ctx, &Functions::new(), expr).ok()
+}
+
+/// Evaluate `expr` against `ctx`, dispatching unknown calls/methods to `funcs`
+///
+/// An identifier that isn't in `ctx` evaluates to `Ok(Value::None)` rather
+/// than failing. An `Err` comes from a malformed or unsupported expression,
+/// an operator applied to operands it isn't defined for, a bad index, or a
+/// call that fails or names no builtin or registered function.
+pub(crate) fn eval_with(
+    ctx: &BTreeMap<String, Expr>,
+    funcs: &Functions,
+    expr: &Expr,
+) -> Result<Value, EvalError> {
+    use crate::operator::Operator;
+
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(i) => i.base10_parse().map(Value::Int).map_err(|_| EvalError::ParseError),
+            Lit::Float(f) => f.base10_parse().map(Value::Float).map_err(|_| EvalError::ParseError),
+            Lit::Str(s) => Ok(Value::Str(s.value())),
+            Lit::Bool(b) => Ok(Value::Bool(b.value)),
+            _ => Err(EvalError::ParseError),
+        },
+        Expr::Path(p) => {
+            let ident = p
+                .path
+                .get_ident()
+                .ok_or_else(|| EvalError::UnknownIdent(path_str(&p.path)))?
+                .to_string();
+            match ident.as_str() {
+                "None" => Ok(Value::None),
+                _ => match ctx.get(&ident) {
+                    Some(e) => eval_with(ctx, funcs, e),
+                    None => Ok(Value::None),
+                },
+            }
+        }
+        Expr::Paren(p) => eval_with(ctx, funcs, &p.expr),
+        Expr::Group(g) => eval_with(ctx, funcs, &g.expr),
+        Expr::Reference(r) => eval_with(ctx, funcs, &r.expr),
+        Expr::Unary(u) => {
+            let v = eval_with(ctx, funcs, &u.expr)?;
+            let (op, sentinel) = match u.op {
+                UnOp::Neg(_) => (Operator::Neg, Value::Int(0)),
+                UnOp::Not(_) => (Operator::Not, Value::Bool(false)),
+                _ => return Err(EvalError::ParseError),
+            };
+            let mut stack = vec![v, sentinel];
+            op.eval(&mut stack)?;
+            stack.pop().ok_or(EvalError::Arity)
+        }
+        Expr::Binary(b) => {
+            let lhs = eval_with(ctx, funcs, &b.left)?;
+            let rhs = eval_with(ctx, funcs, &b.right)?;
+            let op = Operator::try_from(b.op).map_err(|_| EvalError::ParseError)?;
+            let mut stack = vec![lhs, rhs];
+            op.eval(&mut stack)?;
+            stack.pop().ok_or(EvalError::Arity)
+        }
+        Expr::Range(r) => {
+            let from = match r.from.as_deref() {
+                Some(e) => eval_with(ctx, funcs, e)?,
+                None => return Err(EvalError::ParseError),
+            };
+            let to = match r.to.as_deref() {
+                Some(e) => eval_with(ctx, funcs, e)?,
+                None => return Err(EvalError::ParseError),
+            };
+            match (from, to) {
+                (Value::Int(from), Value::Int(to)) => Ok(Value::Range(from..to)),
+                (lhs, rhs) => Err(EvalError::TypeMismatch { op: "..", lhs, rhs }),
+            }
+        }
+        Expr::Array(a) => {
+            let mut values = Vec::with_capacity(a.elems.len());
+            for e in &a.elems {
+                match eval_with(ctx, funcs, e)? {
+                    Value::None => return Err(none_in_collection(ctx, e)),
+                    v => values.push(v),
+                }
+            }
+            Ok(Value::Vec(values))
+        }
+        Expr::Index(i) => {
+            let base = eval_with(ctx, funcs, &i.expr)?;
+            let index = eval_with(ctx, funcs, &i.index)?;
+            index_value(base, index)
+        }
+        Expr::Struct(s) => {
+            let mut map = match &s.rest {
+                Some(rest) => match eval_with(ctx, funcs, rest)? {
+                    Value::Map(m) => m,
+                    other => {
+                        return Err(EvalError::TypeMismatch {
+                            op: "..",
+                            lhs: other,
+                            rhs: Value::Map(BTreeMap::new()),
+                        })
+                    }
+                },
+                None => BTreeMap::new(),
+            };
+            for field in &s.fields {
+                let key = member_str(&field.member);
+                match eval_with(ctx, funcs, &field.expr)? {
+                    Value::None => return Err(none_in_collection(ctx, &field.expr)),
+                    v => {
+                        map.insert(key, v);
+                    }
+                }
+            }
+            Ok(Value::Map(map))
+        }
+        Expr::Field(field) => {
+            let base = eval_with(ctx, funcs, &field.base)?;
+            let key = member_str(&field.member);
+            match base {
+                Value::Map(m) => Ok(m.get(&key).cloned().unwrap_or(Value::None)),
+                Value::None => Ok(Value::None),
+                other => Err(EvalError::TypeMismatch {
+                    op: ".",
+                    lhs: other,
+                    rhs: Value::Str(key),
+                }),
+            }
+        }
+        Expr::Call(c) => {
+            if let Expr::Path(p) = &*c.func {
+                let ident = p
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| EvalError::UnknownIdent(path_str(&p.path)))?
+                    .to_string();
+                let args = c
+                    .args
+                    .iter()
+                    .map(|a| eval_with(ctx, funcs, a))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if ident == "Some" {
+                    let [v]: [Value; 1] = args.try_into().map_err(|_| EvalError::Arity)?;
+                    return Ok(Value::Option(Box::new(v)));
+                }
+
+                return funcs
+                    .get(&ident)
+                    .ok_or_else(|| EvalError::UnknownIdent(ident.clone()))?(&args)
+                .ok_or(EvalError::Arity);
+            }
+            Err(EvalError::ParseError)
+        }
+        Expr::MethodCall(m) => {
+            let receiver = eval_with(ctx, funcs, &m.receiver)?;
+            let args = m
+                .args
+                .iter()
+                .map(|a| eval_with(ctx, funcs, a))
+                .collect::<Result<Vec<_>, _>>()?;
+            let name = m.method.to_string();
+
+            match method::eval(receiver.clone(), &name, &args) {
+                Some(result) => result,
+                None => match funcs.get(&name) {
+                    Some(f) => {
+                        let mut all = vec![receiver];
+                        all.extend(args);
+                        f(&all).ok_or(EvalError::Arity)
+                    }
+                    None => Err(EvalError::UnknownIdent(name)),
+                },
+            }
+        }
+        // Desugared from `??` (see `crate::desugar`): `=` is Rust's loosest-
+        // binding, right-associative construct, so this is what actually
+        // gives `??` its documented "loosest of all operators" precedence.
+        // The right side is only evaluated - and only its errors surface -
+        // when the left side is `Value::None`, making this a true short
+        // circuit rather than just a pick-a-value fallback.
+        Expr::Assign(a) => {
+            let lhs = eval_with(ctx, funcs, &a.left)?;
+            if lhs == Value::None {
+                eval_with(ctx, funcs, &a.right)
+            } else {
+                Ok(lhs)
+            }
+        }
+        _ => Err(EvalError::UnknownIdent(expr_str(expr))),
+    }
+}
+
+fn path_str(path: &syn::Path) -> String {
+    quote::quote!(#path).to_string()
+}
+
+fn expr_str(expr: &Expr) -> String {
+    quote::quote!(#expr).to_string()
+}
+
+/// Distinguish a genuinely unresolved identifier from any other route to
+/// `Value::None` (an explicit `None` literal, a field access on an
+/// unresolved value, ...) when a collection literal can't hold either
+fn none_in_collection(ctx: &BTreeMap<String, Expr>, expr: &Expr) -> EvalError {
+    match expr {
+        Expr::Path(p) => match p.path.get_ident() {
+            Some(ident) if ident != "None" && !ctx.contains_key(&ident.to_string()) => {
+                EvalError::UnknownIdent(ident.to_string())
+            }
+            _ => EvalError::NullElement,
+        },
+        _ => EvalError::NullElement,
+    }
+}
+
+fn member_str(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+fn index_value(base: Value, index: Value) -> Result<Value, EvalError> {
+    match (&base, &index) {
+        (Value::Vec(v), Value::Int(i)) => usize::try_from(*i)
+            .ok()
+            .and_then(|i| v.get(i).cloned())
+            .ok_or(EvalError::IndexOutOfBounds),
+        (Value::Vec(v), Value::Range(r)) => usize::try_from(r.start)
+            .ok()
+            .zip(usize::try_from(r.end).ok())
+            .and_then(|(start, end)| v.get(start..end).map(|s| Value::Vec(s.to_vec())))
+            .ok_or(EvalError::IndexOutOfBounds),
+        (Value::Str(s), Value::Int(i)) => usize::try_from(*i)
+            .ok()
+            .and_then(|i| s.chars().nth(i))
+            .map(|c| Value::Str(c.to_string()))
+            .ok_or(EvalError::IndexOutOfBounds),
+        (Value::Str(s), Value::Range(r)) => usize::try_from(r.start)
+            .ok()
+            .zip(usize::try_from(r.end).ok())
+            .and_then(|(start, end)| s.get(start..end).map(|s| Value::Str(s.to_owned())))
+            .ok_or(EvalError::IndexOutOfBounds),
+        _ => Err(EvalError::TypeMismatch { op: "[]", lhs: base, rhs: index }),
+    }
+}
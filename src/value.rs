@@ -0,0 +1,360 @@
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt,
+    ops::{Add, Div, Mul, Range, Rem, Sub},
+};
+
+/// A loosely-typed value produced while evaluating an expression
+///
+/// Every variant is implicitly optional: an identifier that isn't in the
+/// context, or an explicit `None`, collapses to [`Value::None`] rather than
+/// failing outright, so callers can keep chaining `Option`-style methods on
+/// it (see the crate docs).
+#[derive(Debug, Clone)]
+pub enum Value {
+    None,
+    /// A value that came from an explicit `Some(..)` in the source, kept
+    /// boxed so `.is_option()` can still tell it apart from a plain value
+    /// after it has been evaluated.
+    Option(Box<Value>),
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Vec(Vec<Value>),
+    Range(Range<i64>),
+    /// Keyed/struct-like data, accessed through dotted field expressions
+    /// (`user.age`), constructed from a struct literal (`User { age: 20 }`)
+    /// - there's no dedicated object-literal syntax, so this crate reuses
+    /// Rust's own struct-literal grammar instead.
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Peel away any `Some(..)` wrapper, returning the innermost concrete value
+    pub(crate) fn flatten(self) -> Value {
+        match self {
+            Value::Option(v) => v.flatten(),
+            v => v,
+        }
+    }
+
+    /// Are `self` and `other` the same variant, ignoring the contained value?
+    pub fn is_same(&self, other: &Value) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::Bool(_))
+    }
+
+    pub fn is_int(&self) -> bool {
+        matches!(self, Value::Int(_))
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Value::Float(_))
+    }
+
+    /// Is this an `Int` or a `Float`? Lets arithmetic and comparisons mix the two.
+    pub(crate) fn is_numeric(&self) -> bool {
+        matches!(self, Value::Int(_) | Value::Float(_))
+    }
+
+    pub fn is_str(&self) -> bool {
+        matches!(self, Value::Str(_))
+    }
+
+    pub fn is_vec(&self) -> bool {
+        matches!(self, Value::Vec(_))
+    }
+
+    pub fn is_range(&self) -> bool {
+        matches!(self, Value::Range(_))
+    }
+
+    pub fn is_map(&self) -> bool {
+        matches!(self, Value::Map(_))
+    }
+
+    /// Was this value produced from `None`/`Some(..)`, or an unresolved identifier?
+    pub fn is_option(&self) -> bool {
+        matches!(self, Value::None | Value::Option(_))
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Value::None)
+    }
+
+    /// Alias for [`Value::is_none`], for callers coming from a SQL/null-coalescing background
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::None)
+    }
+
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    /// `Option::unwrap`-like: fails (returns `None`) only when `self` is `Value::None`
+    pub fn unwrap(self) -> Option<Value> {
+        match self {
+            Value::None => None,
+            other => Some(other.flatten()),
+        }
+    }
+
+    pub fn unwrap_or(self, other: &Value) -> Value {
+        if self.is_none() {
+            other.clone().flatten()
+        } else {
+            self.flatten()
+        }
+    }
+
+    /// `Option::and`-like short circuit: `None` if `self` is `None`, else `other`
+    ///
+    /// Used by the `.and()` method; the `&&` operator uses [`Value::and`] instead.
+    pub(crate) fn opt_and(&self, other: &Value) -> Option<Value> {
+        if self.is_none() {
+            None
+        } else {
+            Some(other.clone().flatten())
+        }
+    }
+
+    /// `Option::or`-like short circuit: `self` if defined, else `other`
+    ///
+    /// Used by the `.or()` method; the `||` operator uses [`Value::or`] instead.
+    pub(crate) fn opt_or(&self, other: &Value) -> Option<Value> {
+        if self.is_some() {
+            Some(self.clone().flatten())
+        } else if other.is_some() {
+            Some(other.clone().flatten())
+        } else {
+            None
+        }
+    }
+
+    /// `Option::xor`-like: the defined side, only if exactly one side is defined
+    pub(crate) fn xor(&self, other: &Value) -> Option<Value> {
+        match (self.is_some(), other.is_some()) {
+            (true, false) => Some(self.clone().flatten()),
+            (false, true) => Some(other.clone().flatten()),
+            _ => None,
+        }
+    }
+
+    /// Boolean `&&`, as used by the `&&` operator (both sides are always `Bool`)
+    pub(crate) fn and(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => Value::Bool(*a && *b),
+            _ => Value::None,
+        }
+    }
+
+    /// Boolean `||`, as used by the `||` operator (both sides are always `Bool`)
+    pub(crate) fn or(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => Value::Bool(*a || *b),
+            _ => Value::None,
+        }
+    }
+
+    pub(crate) fn not(self) -> Value {
+        match self {
+            Value::Bool(b) => Value::Bool(!b),
+            v => v,
+        }
+    }
+
+    pub(crate) fn neg(self) -> Value {
+        match self {
+            Value::Int(i) => Value::Int(-i),
+            Value::Float(f) => Value::Float(-f),
+            v => v,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::None, Value::None) => true,
+            (Value::Option(a), Value::Option(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::Int(a), Value::Float(b)) | (Value::Float(b), Value::Int(a)) => *a as f64 == *b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Vec(a), Value::Vec(b)) => a == b,
+            (Value::Range(a), Value::Range(b)) => a == b,
+            (Value::Map(a), Value::Map(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Str(a), Value::Str(b)) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl Add for Value {
+    type Output = Value;
+
+    fn add(self, rhs: Value) -> Value {
+        match (self, rhs) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a + b),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a + b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 + b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a + b as f64),
+            (Value::Str(a), Value::Str(b)) => Value::Str(a + &b),
+            _ => Value::None,
+        }
+    }
+}
+
+impl Sub for Value {
+    type Output = Value;
+
+    fn sub(self, rhs: Value) -> Value {
+        match (self, rhs) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a - b),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a - b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 - b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a - b as f64),
+            _ => Value::None,
+        }
+    }
+}
+
+impl Mul for Value {
+    type Output = Value;
+
+    fn mul(self, rhs: Value) -> Value {
+        match (self, rhs) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a * b),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a * b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 * b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a * b as f64),
+            (Value::Str(s), Value::Int(n)) | (Value::Int(n), Value::Str(s)) => {
+                Value::Str(s.repeat(n.max(0) as usize))
+            }
+            _ => Value::None,
+        }
+    }
+}
+
+impl Div for Value {
+    type Output = Value;
+
+    fn div(self, rhs: Value) -> Value {
+        match (self, rhs) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a / b),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a / b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 / b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a / b as f64),
+            _ => Value::None,
+        }
+    }
+}
+
+impl Rem for Value {
+    type Output = Value;
+
+    fn rem(self, rhs: Value) -> Value {
+        match (self, rhs) {
+            (Value::Int(a), Value::Int(b)) => Value::Int(a % b),
+            (Value::Float(a), Value::Float(b)) => Value::Float(a % b),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 % b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a % b as f64),
+            _ => Value::None,
+        }
+    }
+}
+
+impl Value {
+    /// Render `self` back into Rust source that re-parses to an equal value
+    ///
+    /// Used by [`crate::Eval::eval_block`] to fold an evaluated `let` binding
+    /// back into the scratch context as a context entry. Differs from
+    /// [`Display`](fmt::Display) in that floats always carry an explicit
+    /// `f64` suffix, so `2.0` doesn't come back as the integer `2` (infinite
+    /// and NaN floats, and `i64::MIN`, are written as small arithmetic
+    /// expressions), collections have no trailing comma, and maps are
+    /// written as a `Map{..}` struct literal.
+    pub(crate) fn literal(&self) -> String {
+        match self {
+            Value::None => "None".to_owned(),
+            Value::Option(v) => format!("Some({})", v.literal()),
+            Value::Bool(b) => b.to_string(),
+            Value::Int(i) if *i == i64::MIN => format!("({}-1)", i64::MIN + 1),
+            Value::Int(i) => i.to_string(),
+            Value::Float(n) if n.is_nan() => "(0f64/0f64)".to_owned(),
+            Value::Float(n) if n.is_infinite() => {
+                format!("({}1f64/0f64)", if *n < 0.0 { "-" } else { "" })
+            }
+            Value::Float(n) => format!("{}f64", n),
+            Value::Str(s) => format!("{:?}", s),
+            Value::Range(r) => format!("{}..{}", r.start, r.end),
+            Value::Vec(v) => format!(
+                "[{}]",
+                v.iter().map(Value::literal).collect::<Vec<_>>().join(",")
+            ),
+            // Mirrors the struct-literal syntax `reflect::eval_with` parses a
+            // `Value::Map` out of - "Map" is just a placeholder struct name,
+            // never looked up.
+            Value::Map(m) => format!(
+                "Map{{{}}}",
+                m.iter()
+                    .map(|(k, v)| format!("{}:{}", k, v.literal()))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::None => write!(f, "None"),
+            Value::Option(v) => write!(f, "Some({})", v),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(n) => write!(f, "{}", n),
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::Range(r) => write!(f, "{}..{}", r.start, r.end),
+            Value::Vec(v) => {
+                write!(f, "[")?;
+                for item in v {
+                    write!(f, "{},", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(m) => {
+                write!(f, "{{")?;
+                for (k, v) in m {
+                    write!(f, "{}: {},", k, v)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}